@@ -0,0 +1,139 @@
+use std::{
+    pin::Pin,
+    sync::{Condvar, Mutex, MutexGuard, WaitTimeoutResult},
+    time::Duration,
+};
+
+use crate::{PinLockGuard, PoisonError};
+
+pub struct PinCondvar {
+    inner: Condvar,
+}
+
+impl PinCondvar {
+    /// Creates a new condition variable which is ready to be waited on and
+    /// notified.
+    pub const fn new() -> PinCondvar {
+        PinCondvar {
+            inner: Condvar::new(),
+        }
+    }
+
+    /// Blocks the current thread until this condition variable receives a
+    /// notification.
+    ///
+    /// This function will atomically unlock the mutex specified (represented
+    /// by `guard`) and block the current thread. This means that any calls to
+    /// `notify_one` or `notify_all` which happen logically after the mutex is
+    /// unlocked are candidates to wake this thread up.
+    ///
+    /// The returned guard re-establishes the same pin projection as the one
+    /// passed in, since the underlying `std::sync::Condvar::wait` only
+    /// understands plain `MutexGuard`s and would otherwise strip the `Pin`
+    /// wrapper from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PoisonError` if the mutex protecting `guard` was poisoned by
+    /// a prior holder that panicked while holding it, mirroring
+    /// [`PinLock::lock`](crate::PinLock::lock).
+    pub fn wait<'a, T>(
+        &self,
+        guard: PinLockGuard<'a, T, Mutex<T>>,
+    ) -> Result<PinLockGuard<'a, T, Mutex<T>>, PoisonError>
+    where
+        T: 'a,
+    {
+        // SAFETY: `guard.inner` is a `Pin<MutexGuard<'a, T>>` that was itself
+        // constructed from `Pin::new_unchecked` in `PinLock::lock`; unwrapping
+        // it here to pass to `std::sync::Condvar::wait` does not move the
+        // pinned value, since `wait` only re-locks the same mutex and hands
+        // the same `MutexGuard` back.
+        let guard: MutexGuard<'a, T> = unsafe { Pin::into_inner_unchecked(guard.inner) };
+
+        let guard = self.inner.wait(guard).map_err(|_| PoisonError)?;
+
+        // SAFETY: see `PinLock::lock` for the pin projection safety argument;
+        // it applies unchanged here since `guard` still protects the same,
+        // never-moved `T`.
+        Ok(PinLockGuard {
+            inner: unsafe { Pin::new_unchecked(guard) },
+        })
+    }
+
+    /// Like [`PinCondvar::wait`], but with a timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PoisonError` if the mutex protecting `guard` was poisoned by
+    /// a prior holder that panicked while holding it.
+    pub fn wait_timeout<'a, T>(
+        &self,
+        guard: PinLockGuard<'a, T, Mutex<T>>,
+        dur: Duration,
+    ) -> Result<(PinLockGuard<'a, T, Mutex<T>>, WaitTimeoutResult), PoisonError>
+    where
+        T: 'a,
+    {
+        // SAFETY: see `PinCondvar::wait`
+        let guard: MutexGuard<'a, T> = unsafe { Pin::into_inner_unchecked(guard.inner) };
+
+        let (guard, result) = self.inner.wait_timeout(guard, dur).map_err(|_| PoisonError)?;
+
+        // SAFETY: see `PinLock::lock`
+        let guard = PinLockGuard {
+            inner: unsafe { Pin::new_unchecked(guard) },
+        };
+
+        Ok((guard, result))
+    }
+
+    /// Like [`PinCondvar::wait`], but blocks while `condition` returns `true`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PoisonError` if the mutex protecting `guard` was poisoned by
+    /// a prior holder that panicked while holding it.
+    pub fn wait_while<'a, T>(
+        &self,
+        guard: PinLockGuard<'a, T, Mutex<T>>,
+        mut condition: impl FnMut(Pin<&mut T>) -> bool,
+    ) -> Result<PinLockGuard<'a, T, Mutex<T>>, PoisonError>
+    where
+        T: 'a,
+    {
+        // SAFETY: see `PinCondvar::wait`
+        let guard: MutexGuard<'a, T> = unsafe { Pin::into_inner_unchecked(guard.inner) };
+
+        let guard = self
+            .inner
+            .wait_while(guard, |value| {
+                // SAFETY: `value` is reborrowed from a `MutexGuard` that was
+                // itself projected from a `Pin<&PinLock<T>>`, so it is sound
+                // to hand the condition a pinned reference back.
+                condition(unsafe { Pin::new_unchecked(value) })
+            })
+            .map_err(|_| PoisonError)?;
+
+        // SAFETY: see `PinLock::lock`
+        Ok(PinLockGuard {
+            inner: unsafe { Pin::new_unchecked(guard) },
+        })
+    }
+
+    /// Wakes up one blocked thread on this condvar.
+    pub fn notify_one(&self) {
+        self.inner.notify_one();
+    }
+
+    /// Wakes up all blocked threads on this condvar.
+    pub fn notify_all(&self) {
+        self.inner.notify_all();
+    }
+}
+
+impl Default for PinCondvar {
+    fn default() -> Self {
+        PinCondvar::new()
+    }
+}
@@ -0,0 +1,193 @@
+use core::{mem::MaybeUninit, pin::Pin, ptr::addr_of_mut};
+
+#[cfg(feature = "std")]
+use std::boxed::Box;
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+use crate::{raw::RawPinLock, PinLock};
+
+/// An in-place initializer for `T`.
+///
+/// Rather than constructing a `T` on the stack and moving it into place,
+/// implementors of this trait write directly into the final, pinned memory
+/// location. This is what makes it possible to initialize self-referential
+/// values that must never move once their address is fixed.
+///
+/// # Safety
+///
+/// `slot` is a valid, properly aligned pointer to uninitialized memory large
+/// enough to hold a `T`. Implementations must fully initialize `*slot` before
+/// returning `Ok(())`, and must not read from `slot` before it has done so.
+/// If `Err` is returned, `*slot` must be left uninitialized, since the caller
+/// will not run `T`'s destructor over it.
+pub unsafe trait PinInit<T, E = core::convert::Infallible> {
+    /// Initialize `slot`.
+    ///
+    /// # Safety
+    ///
+    /// `slot` must be a valid pointer to uninitialized memory for `T`.
+    unsafe fn __pinned_init(self, slot: *mut T) -> Result<(), E>;
+}
+
+unsafe impl<T, E, F> PinInit<T, E> for F
+where
+    F: FnOnce(*mut T) -> Result<(), E>,
+{
+    unsafe fn __pinned_init(self, slot: *mut T) -> Result<(), E> {
+        self(slot)
+    }
+}
+
+/// Creates a new [`PinInit`] from the given closure.
+///
+/// # Safety
+///
+/// The closure must fully initialize the pointer it is given before
+/// returning `Ok(())`, and must not read from it beforehand.
+pub unsafe fn init<T, E>(f: impl FnOnce(*mut T) -> Result<(), E>) -> impl PinInit<T, E> {
+    f
+}
+
+/// A [`RawPinLock`] backend that can be built in place around a
+/// not-yet-initialized `T`, by exposing where that `T` will ultimately live
+/// before the rest of the backend is built.
+///
+/// This is what lets [`PinLock::try_pin_init`] avoid ever moving a
+/// self-referential `T` into place: the initializer writes directly into
+/// the backend's future data slot, and only once that succeeds is the rest
+/// of the backend (e.g. its lock state) built around it.
+///
+/// `std::sync::Mutex` does not offer an entry point like this — there is no
+/// way to obtain a pointer to the `T` living inside it before calling
+/// `Mutex::new`, which takes `T` by value — so it does not implement this
+/// trait, and `try_pin_init` is unavailable for the default, `Mutex`-backed
+/// `PinLock`.
+///
+/// # Safety
+///
+/// `data_ptr` must return a pointer into `uninit`'s eventual data storage,
+/// valid for writes, that remains at the same address once `finish_init` has
+/// run. Implementations must not read through the returned pointer before
+/// the caller has written a `T` to it. `finish_init` must only be called
+/// after the pointer returned by `data_ptr` has been fully initialized with
+/// a `T`, and must initialize the remainder of `*uninit` (e.g. lock state)
+/// without moving or otherwise touching the data it was given.
+pub unsafe trait RawPinInit<T>: RawPinLock<T> {
+    /// Returns a pointer to where `T` will live inside `uninit`, a pointer to
+    /// memory sized and aligned for `Self` that is not yet initialized.
+    ///
+    /// # Safety
+    ///
+    /// `uninit` must be a valid pointer to memory sized and aligned for
+    /// `Self` that has not yet been initialized.
+    unsafe fn data_ptr(uninit: *mut Self) -> *mut T;
+
+    /// Finishes initializing `*uninit`, given that the pointer returned by
+    /// `data_ptr` has already been written with a valid `T`.
+    ///
+    /// # Safety
+    ///
+    /// `uninit` must be the same pointer most recently passed to
+    /// `data_ptr`, and the `T` at that pointer must already be fully
+    /// initialized.
+    unsafe fn finish_init(uninit: *mut Self);
+}
+
+impl<T, R: RawPinInit<T>> PinLock<T, R> {
+    /// Constructs a new `PinLock<T, R>` in-place, pinned on the heap, using
+    /// the given initializer to write `T` directly into its final location.
+    ///
+    /// Unlike [`PinLock::new`], this never constructs `T` on the stack and
+    /// moves it into the lock, so it is suitable for self-referential `T`
+    /// that must never move after their address is fixed. This is only
+    /// available for backends `R` that implement [`RawPinInit`], since doing
+    /// this soundly requires the backend to expose its future data slot
+    /// before it is otherwise built.
+    ///
+    /// If `init` returns `Err`, the partially allocated `PinLock` is dropped
+    /// without running `T`'s destructor, since `init` is required to leave
+    /// the slot uninitialized on failure.
+    pub fn try_pin_init<E>(init: impl PinInit<T, E>) -> Result<Pin<Box<PinLock<T, R>>>, E> {
+        let mut boxed = Box::new(MaybeUninit::<PinLock<T, R>>::uninit());
+
+        // SAFETY: `boxed` points at memory sized and aligned for
+        // `PinLock<T, R>`, whose only non-zero-sized field is `inner: R`, so
+        // this is a valid pointer to (uninitialized) `R` storage.
+        let raw: *mut R = unsafe { addr_of_mut!((*boxed.as_mut_ptr()).inner) };
+
+        // SAFETY: `raw` is a valid pointer to uninitialized `R` storage, as
+        // `RawPinInit::data_ptr` requires.
+        let slot: *mut T = unsafe { R::data_ptr(raw) };
+
+        // SAFETY: `slot` is the pointer `R::data_ptr` promises `T` will live
+        // at, and `PinInit` requires `init` to fully initialize it before
+        // returning `Ok`.
+        match unsafe { init.__pinned_init(slot) } {
+            Ok(()) => {
+                // SAFETY: `slot` has just been initialized, which is exactly
+                // what `finish_init` requires before building the rest of
+                // `R` around it.
+                unsafe { R::finish_init(raw) };
+
+                // SAFETY: `inner` and the data it protects are now both
+                // initialized, so the whole `PinLock<T, R>` is too.
+                let boxed = unsafe { Box::from_raw(Box::into_raw(boxed).cast::<PinLock<T, R>>()) };
+
+                // SAFETY: the value is heap allocated and will never be
+                // moved again, and `PinLock` does not implement `Unpin`
+                // unconditionally.
+                Ok(unsafe { Pin::new_unchecked(boxed) })
+            }
+            Err(e) => {
+                // `T` was never initialized, so `boxed` must be dropped
+                // without running `T`'s destructor; `MaybeUninit<PinLock<T, R>>`
+                // already guarantees that.
+                drop(boxed);
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "spin"))]
+mod tests {
+    use core::{cell::Cell, convert::Infallible};
+
+    use crate::{init, PinSpinLock};
+
+    #[test]
+    fn try_pin_init_ok_initializes_and_locks() {
+        let lock = PinSpinLock::<u32>::try_pin_init(unsafe {
+            init::<u32, Infallible>(|slot| {
+                slot.write(42);
+                Ok(())
+            })
+        })
+        .unwrap();
+
+        let guard = lock.as_ref().lock().unwrap();
+        assert_eq!(*guard, 42);
+    }
+
+    #[test]
+    fn try_pin_init_err_does_not_drop_the_uninitialized_value() {
+        struct DropFlag<'a>(&'a Cell<bool>);
+
+        impl Drop for DropFlag<'_> {
+            fn drop(&mut self) {
+                self.0.set(true);
+            }
+        }
+
+        let dropped = Cell::new(false);
+
+        let result = PinSpinLock::<DropFlag<'_>>::try_pin_init(unsafe {
+            init::<DropFlag<'_>, &'static str>(|_slot| Err("init failed"))
+        });
+
+        assert!(result.is_err());
+        assert!(!dropped.get());
+    }
+}
@@ -1,56 +1,157 @@
-//! https://docs.rs/pin-cell/latest/pin_cell/struct.PinCell.html but for [`Mutex`] instead of [`RefCell`](std::cell::RefCell)
+//! https://docs.rs/pin-cell/latest/pin_cell/struct.PinCell.html but for [`Mutex`](std::sync::Mutex) instead of [`RefCell`](std::cell::RefCell)
+//!
+//! Only [`init`], [`PinInit`], [`RawPinInit`], [`RawPinLock`], [`PoisonError`],
+//! the generic [`PinLock<T, R>`](PinLock) and [`PinLockGuard`] (with no default
+//! backend), and the `spin` feature's [`SpinMutex`]-backed types are available
+//! without the (default-on) `std` feature; the `Mutex`-backed [`PinLock`]
+//! default, [`PinCondvar`], and [`PinRwLock`] all require it.
 
-use std::{
-    ops::Deref,
-    pin::Pin,
-    sync::{Mutex, MutexGuard},
-};
+#![cfg_attr(not(feature = "std"), no_std)]
 
-pub struct PinLock<T: ?Sized> {
-    inner: Mutex<T>,
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+use core::{marker::PhantomData, ops::Deref, pin::Pin};
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+#[cfg(feature = "std")]
+mod condvar;
+mod init;
+mod raw;
+#[cfg(feature = "std")]
+mod rwlock;
+#[cfg(feature = "spin")]
+mod spin;
+
+#[cfg(feature = "std")]
+pub use condvar::PinCondvar;
+pub use init::{init, PinInit, RawPinInit};
+pub use raw::{PoisonError, RawPinLock};
+#[cfg(feature = "std")]
+pub use rwlock::{PinRwLock, PinRwLockReadGuard, PinRwLockWriteGuard};
+#[cfg(feature = "spin")]
+pub use spin::{PinSpinLock, PinSpinLockGuard, SpinMutex};
+
+#[cfg(feature = "std")]
+pub struct PinLock<T: ?Sized, R: ?Sized = Mutex<T>> {
+    // `fn() -> T` rather than `T` so that `PinLock<T, R>`'s `Send`/`Sync`
+    // depend only on `R`'s own impls (which already encode the right bounds
+    // for `T`), instead of additionally requiring `T: Sync` the way a bare
+    // `PhantomData<T>` would.
+    _marker: PhantomData<fn() -> T>,
+    inner: R,
+}
+
+#[cfg(not(feature = "std"))]
+pub struct PinLock<T: ?Sized, R: ?Sized> {
+    // see the `std`-enabled definition above for why `fn() -> T`
+    _marker: PhantomData<fn() -> T>,
+    inner: R,
 }
 
+#[cfg(feature = "std")]
 impl<T> PinLock<T> {
     /// Creates a new `PinCell` containing `value`.
     pub const fn new(value: T) -> PinLock<T> {
         PinLock {
+            _marker: PhantomData,
             inner: Mutex::new(value),
         }
     }
 }
 
-impl<T: ?Sized> PinLock<T> {
+impl<T: ?Sized, R: ?Sized + RawPinLock<T>> PinLock<T, R> {
+    /// Builds a `PinLock` directly out of an already-constructed raw backend,
+    /// for use with a non-default `R`.
+    pub fn from_raw(raw: R) -> PinLock<T, R>
+    where
+        R: Sized,
+    {
+        PinLock {
+            _marker: PhantomData,
+            inner: raw,
+        }
+    }
+
     /// Acquires a mutex, blocking the current thread until it is able to do so.
     ///
     /// This function will block the local thread until it is available to acquire
     /// the mutex. Upon returning, the thread is the only thread with the lock
     /// held. An RAII guard is returned to allow scoped unlock of the lock. When
     /// the guard goes out of scope, the mutex will be unlocked.
-    pub fn lock<'a>(self: Pin<&'a Self>) -> PinLockGuard<'a, T> {
-        let ref_mut: MutexGuard<'a, T> = Pin::get_ref(self).inner.lock().unwrap();
+    ///
+    /// # Errors
+    ///
+    /// Returns `R::Error` if the backend reports that the lock could not be
+    /// acquired cleanly, e.g. because it was poisoned by a prior holder that
+    /// panicked while holding it.
+    pub fn lock<'a>(self: Pin<&'a Self>) -> Result<PinLockGuard<'a, T, R>, R::Error>
+    where
+        R: 'a,
+        T: 'a,
+    {
+        let ref_mut: R::Guard<'a> = Pin::get_ref(self).inner.lock()?;
 
-        // this is a pin projection from Pin<&PinLock<T>> to Pin<Mutex<T>>
+        // this is a pin projection from Pin<&PinLock<T, R>> to Pin<R::Guard<'a>>
         // projecting is safe because:
         //
-        // - for<T: ?Sized> (PinLock<T>: Unpin) imples (Mutex<T>: Unpin)
+        // - for<T: ?Sized, R: ?Sized> (PinLock<T, R>: Unpin) imples (R: Unpin)
         //   holds true
         // - PinLock does not implement Drop
         //
         // see discussion on tracking issue #49150 about pin projection
         // invariants
-        let pin_ref_mut: Pin<MutexGuard<'a, T>> = unsafe { Pin::new_unchecked(ref_mut) };
+        let pin_ref_mut: Pin<R::Guard<'a>> = unsafe { Pin::new_unchecked(ref_mut) };
+
+        Ok(PinLockGuard { inner: pin_ref_mut })
+    }
+
+    /// Attempts to acquire this mutex without blocking.
+    ///
+    /// Returns `None` if the backend reports that the lock is currently held
+    /// by another thread, or (for poisonable backends) that it is poisoned.
+    pub fn try_lock<'a>(self: Pin<&'a Self>) -> Option<PinLockGuard<'a, T, R>>
+    where
+        R: 'a,
+        T: 'a,
+    {
+        let ref_mut: R::Guard<'a> = Pin::get_ref(self).inner.try_lock()?;
+
+        // see `PinLock::lock` for the pin projection safety argument
+        let pin_ref_mut: Pin<R::Guard<'a>> = unsafe { Pin::new_unchecked(ref_mut) };
 
-        PinLockGuard { inner: pin_ref_mut }
+        Some(PinLockGuard { inner: pin_ref_mut })
     }
 }
 
 #[derive(Debug)]
-/// A wrapper type for a mutably borrowed value from a `PinLock<T>`.
-pub struct PinLockGuard<'a, T: ?Sized> {
-    pub(crate) inner: Pin<MutexGuard<'a, T>>,
+#[cfg(feature = "std")]
+/// A wrapper type for a mutably borrowed value from a `PinLock<T, R>`.
+pub struct PinLockGuard<'a, T: ?Sized, R: ?Sized + RawPinLock<T> = Mutex<T>>
+where
+    R: 'a,
+    T: 'a,
+{
+    pub(crate) inner: Pin<R::Guard<'a>>,
 }
 
-impl<'a, T: ?Sized> Deref for PinLockGuard<'a, T> {
+#[derive(Debug)]
+#[cfg(not(feature = "std"))]
+/// A wrapper type for a mutably borrowed value from a `PinLock<T, R>`.
+pub struct PinLockGuard<'a, T: ?Sized, R: ?Sized + RawPinLock<T>>
+where
+    R: 'a,
+    T: 'a,
+{
+    pub(crate) inner: Pin<R::Guard<'a>>,
+}
+
+impl<'a, T: ?Sized, R: ?Sized + RawPinLock<T>> Deref for PinLockGuard<'a, T, R>
+where
+    R: 'a,
+    T: 'a,
+{
     type Target = T;
 
     fn deref(&self) -> &T {
@@ -58,9 +159,18 @@ impl<'a, T: ?Sized> Deref for PinLockGuard<'a, T> {
     }
 }
 
-impl<'a, T: ?Sized> PinLockGuard<'a, T> {
+impl<'a, T: ?Sized, R: ?Sized + RawPinLock<T>> PinLockGuard<'a, T, R>
+where
+    R: 'a,
+    T: 'a,
+{
     /// Get a pinned mutable reference to the value inside this wrapper.
-    pub fn as_mut<'b>(self: &'b mut PinLockGuard<'a, T>) -> Pin<&'b mut T> {
+    pub fn as_mut<'b>(self: &'b mut PinLockGuard<'a, T, R>) -> Pin<&'b mut T> {
         self.inner.as_mut()
     }
+
+    /// Get a pinned shared reference to the value inside this wrapper.
+    pub fn as_ref(&self) -> Pin<&T> {
+        self.inner.as_ref()
+    }
 }
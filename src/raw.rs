@@ -0,0 +1,61 @@
+use core::{fmt, ops::DerefMut};
+
+/// A backend that `PinLock` can be built on top of.
+///
+/// This follows the same split as [`lock_api`](https://docs.rs/lock_api):
+/// `PinLock<T, R>` is generic over the raw lock `R`, so alternative
+/// implementations (`parking_lot`'s faster, non-poisoning mutexes, or a
+/// `no_std` raw lock) can back the same pinned API as the default,
+/// `std::sync::Mutex`-backed one.
+pub trait RawPinLock<T: ?Sized> {
+    /// The RAII guard returned by [`lock`](RawPinLock::lock) and
+    /// [`try_lock`](RawPinLock::try_lock).
+    type Guard<'a>: DerefMut<Target = T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    /// The error surfaced by [`lock`](RawPinLock::lock) when the lock cannot
+    /// be acquired cleanly (for example, because a prior holder panicked
+    /// while holding it).
+    type Error;
+
+    /// Acquires the lock, blocking the current thread until it is able to do
+    /// so.
+    fn lock(&self) -> Result<Self::Guard<'_>, Self::Error>;
+
+    /// Attempts to acquire the lock without blocking.
+    fn try_lock(&self) -> Option<Self::Guard<'_>>;
+}
+
+/// The error returned by [`RawPinLock::lock`] for backends which, like
+/// `std::sync::Mutex`, poison themselves when a holder panics while the lock
+/// is held.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct PoisonError;
+
+impl fmt::Display for PoisonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a prior holder of this lock panicked while holding it")
+    }
+}
+
+impl core::error::Error for PoisonError {}
+
+#[cfg(feature = "std")]
+impl<T: ?Sized> RawPinLock<T> for std::sync::Mutex<T> {
+    type Guard<'a>
+        = std::sync::MutexGuard<'a, T>
+    where
+        T: 'a;
+    type Error = PoisonError;
+
+    fn lock(&self) -> Result<Self::Guard<'_>, Self::Error> {
+        std::sync::Mutex::lock(self).map_err(|_| PoisonError)
+    }
+
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        std::sync::Mutex::try_lock(self).ok()
+    }
+}
@@ -0,0 +1,106 @@
+use std::{
+    ops::Deref,
+    pin::Pin,
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+pub struct PinRwLock<T: ?Sized> {
+    inner: RwLock<T>,
+}
+
+impl<T> PinRwLock<T> {
+    /// Creates a new `PinRwLock` containing `value`.
+    pub const fn new(value: T) -> PinRwLock<T> {
+        PinRwLock {
+            inner: RwLock::new(value),
+        }
+    }
+}
+
+impl<T: ?Sized> PinRwLock<T> {
+    /// Locks this `PinRwLock` with shared read access, blocking the current
+    /// thread until it can be acquired.
+    ///
+    /// The calling thread will be blocked until there are no more writers
+    /// which hold the lock. There may be other readers currently inside the
+    /// lock when this method returns. This method does not provide any
+    /// guarantees with respect to the ordering of whether contentious readers
+    /// or writers will acquire the lock first.
+    pub fn read<'a>(self: Pin<&'a Self>) -> PinRwLockReadGuard<'a, T> {
+        let ref_: RwLockReadGuard<'a, T> = Pin::get_ref(self).inner.read().unwrap();
+
+        // this is a pin projection from Pin<&PinRwLock<T>> to Pin<RwLockReadGuard<T>>
+        // projecting is safe because:
+        //
+        // - for<T: ?Sized> (PinRwLock<T>: Unpin) imples (RwLock<T>: Unpin)
+        //   holds true
+        // - PinRwLock does not implement Drop
+        //
+        // see discussion on tracking issue #49150 about pin projection
+        // invariants
+        //
+        // additionally, a shared pinned reference is always sound to hand out
+        // since `&T` never allows moving out of `T`
+        let pin_ref: Pin<RwLockReadGuard<'a, T>> = unsafe { Pin::new_unchecked(ref_) };
+
+        PinRwLockReadGuard { inner: pin_ref }
+    }
+
+    /// Locks this `PinRwLock` with exclusive write access, blocking the
+    /// current thread until it can be acquired.
+    ///
+    /// This function will not return while other writers or other readers
+    /// currently have access to the lock.
+    pub fn write<'a>(self: Pin<&'a Self>) -> PinRwLockWriteGuard<'a, T> {
+        let ref_mut: RwLockWriteGuard<'a, T> = Pin::get_ref(self).inner.write().unwrap();
+
+        // see PinRwLock::read for the pin projection safety argument; the
+        // same reasoning applies here since the guard never permits moving
+        // out of `T`
+        let pin_ref_mut: Pin<RwLockWriteGuard<'a, T>> = unsafe { Pin::new_unchecked(ref_mut) };
+
+        PinRwLockWriteGuard { inner: pin_ref_mut }
+    }
+}
+
+#[derive(Debug)]
+/// A wrapper type for a shared, read-only borrowed value from a `PinRwLock<T>`.
+pub struct PinRwLockReadGuard<'a, T: ?Sized> {
+    inner: Pin<RwLockReadGuard<'a, T>>,
+}
+
+impl<'a, T: ?Sized> Deref for PinRwLockReadGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<'a, T: ?Sized> PinRwLockReadGuard<'a, T> {
+    /// Get a pinned shared reference to the value inside this wrapper.
+    pub fn as_ref(&self) -> Pin<&T> {
+        self.inner.as_ref()
+    }
+}
+
+#[derive(Debug)]
+/// A wrapper type for an exclusive, mutably borrowed value from a `PinRwLock<T>`.
+pub struct PinRwLockWriteGuard<'a, T: ?Sized> {
+    pub(crate) inner: Pin<RwLockWriteGuard<'a, T>>,
+}
+
+impl<'a, T: ?Sized> Deref for PinRwLockWriteGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<'a, T: ?Sized> PinRwLockWriteGuard<'a, T> {
+    /// Get a pinned mutable reference to the value inside this wrapper.
+    pub fn as_mut<'b>(self: &'b mut PinRwLockWriteGuard<'a, T>) -> Pin<&'b mut T> {
+        self.inner.as_mut()
+    }
+}
@@ -0,0 +1,140 @@
+use core::{
+    cell::UnsafeCell,
+    convert::Infallible,
+    hint::spin_loop,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::addr_of_mut,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    init::RawPinInit,
+    raw::RawPinLock,
+    PinLock, PinLockGuard,
+};
+
+/// A spinlock-backed [`RawPinLock`], for `no_std` environments without an OS
+/// scheduler to block a thread on.
+///
+/// [`PinSpinLock<T>`](PinSpinLock) is [`PinLock<T, SpinMutex<T>>`](PinLock)
+/// fronted by this backend, exactly as [`std::sync::Mutex`] fronts the
+/// default `PinLock`.
+pub struct SpinMutex<T: ?Sized> {
+    locked: AtomicBool,
+    data: UnsafeCell<T>,
+}
+
+// SAFETY: a `SpinMutex<T>` only ever hands out exclusive access to its `T`
+// while `locked` is held, exactly as `std::sync::Mutex<T>` does, so the same
+// `Send`/`Sync` reasoning applies.
+unsafe impl<T: ?Sized + Send> Send for SpinMutex<T> {}
+unsafe impl<T: ?Sized + Send> Sync for SpinMutex<T> {}
+
+impl<T> SpinMutex<T> {
+    /// Creates a new `SpinMutex` containing `value`.
+    pub const fn new(value: T) -> SpinMutex<T> {
+        SpinMutex {
+            locked: AtomicBool::new(false),
+            data: UnsafeCell::new(value),
+        }
+    }
+}
+
+impl<T: ?Sized> RawPinLock<T> for SpinMutex<T> {
+    type Guard<'a>
+        = SpinMutexGuard<'a, T>
+    where
+        T: 'a;
+    type Error = Infallible;
+
+    /// Acquires the spinlock, busy-waiting the current thread until it is
+    /// able to do so.
+    ///
+    /// There is no OS-level blocking here: the calling thread spins, polling
+    /// the lock state, until it observes the lock as free and wins the
+    /// compare-exchange that claims it.
+    fn lock(&self) -> Result<Self::Guard<'_>, Self::Error> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            while self.locked.load(Ordering::Relaxed) {
+                spin_loop();
+            }
+        }
+
+        Ok(SpinMutexGuard { lock: self })
+    }
+
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        self.locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| SpinMutexGuard { lock: self })
+    }
+}
+
+// SAFETY: `data` is the only field whose initialization depends on `T`, and
+// `UnsafeCell<T>` is documented to have the same in-memory representation as
+// `T`, so a pointer to it may be cast directly to `*mut T`. `finish_init`
+// only touches `locked`, leaving the now-initialized `data` untouched.
+unsafe impl<T> RawPinInit<T> for SpinMutex<T> {
+    unsafe fn data_ptr(uninit: *mut Self) -> *mut T {
+        // SAFETY: see the impl-level safety comment.
+        unsafe { addr_of_mut!((*uninit).data).cast::<T>() }
+    }
+
+    unsafe fn finish_init(uninit: *mut Self) {
+        // SAFETY: see the impl-level safety comment.
+        unsafe { addr_of_mut!((*uninit).locked).write(AtomicBool::new(false)) };
+    }
+}
+
+/// The RAII guard returned by [`SpinMutex::lock`](RawPinLock::lock) and
+/// [`SpinMutex::try_lock`](RawPinLock::try_lock).
+pub struct SpinMutexGuard<'a, T: ?Sized> {
+    lock: &'a SpinMutex<T>,
+}
+
+impl<'a, T: ?Sized> Deref for SpinMutexGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: holding a `SpinMutexGuard` means we won the compare-exchange
+        // that set `locked`, so we have exclusive access to `data` until we
+        // release it on drop.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<'a, T: ?Sized> DerefMut for SpinMutexGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: see `Deref::deref`.
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<'a, T: ?Sized> Drop for SpinMutexGuard<'a, T> {
+    fn drop(&mut self) {
+        self.lock.locked.store(false, Ordering::Release);
+    }
+}
+
+/// A spinlock-backed mirror of [`PinLock`], for `no_std` environments
+/// without an OS scheduler to block a thread on.
+pub type PinSpinLock<T> = PinLock<T, SpinMutex<T>>;
+
+/// A wrapper type for a mutably borrowed value from a `PinSpinLock<T>`.
+pub type PinSpinLockGuard<'a, T> = PinLockGuard<'a, T, SpinMutex<T>>;
+
+impl<T> PinSpinLock<T> {
+    /// Creates a new `PinSpinLock` containing `value`.
+    pub const fn new(value: T) -> PinSpinLock<T> {
+        PinLock {
+            _marker: PhantomData,
+            inner: SpinMutex::new(value),
+        }
+    }
+}